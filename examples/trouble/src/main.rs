@@ -5,9 +5,16 @@ use {defmt_rtt as _, panic_probe as _};
 
 use defmt::{info, warn};
 use embassy_executor::Spawner;
-use embassy_futures::select::select;
-use embassy_time::Timer;
-use microbit_bsp::{ble::MultiprotocolServiceLayer, Config, Microbit};
+use embassy_futures::select::{select, select3, Either};
+use embassy_time::{Duration, Instant, Timer};
+use microbit_bsp::{
+    battery::BatteryMonitor,
+    ble::{MultiprotocolServiceLayer, Scanner},
+    dis,
+    dis::DeviceInformationService,
+    display::{connection_indicator, ConnectionState, ConnectionStateSignal},
+    Config, LedMatrix, Microbit,
+};
 use trouble_host::prelude::*;
 
 /// Size of L2CAP packets (ATT MTU is this - 4)
@@ -25,6 +32,7 @@ type Resources<C> = HostResources<C, CONNECTIONS_MAX, L2CAP_CHANNELS_MAX, L2CAP_
 #[gatt_server]
 struct Server {
     battery_service: BatteryService,
+    device_information_service: DeviceInformationService,
 }
 
 // Battery service
@@ -43,31 +51,41 @@ async fn mpsl_task(mpsl: &'static MultiprotocolServiceLayer<'static>) -> ! {
     mpsl.run().await
 }
 
+/// Signal used to report connection state transitions to [`display_task`].
+static CONN_STATE: ConnectionStateSignal = ConnectionStateSignal::new();
+
+#[embassy_executor::task]
+async fn display_task(mut display: LedMatrix) -> ! {
+    connection_indicator(&mut display, &CONN_STATE).await
+}
+
 #[embassy_executor::main]
 async fn main(spawner: Spawner) {
     let board = Microbit::new(Config::default());
     let (sdc, mpsl) = board
         .ble
-        .init(board.timer0, board.rng)
+        .init(board.ble_resources, board.rng)
         .expect("BLE Stack failed to initialize");
     spawner.must_spawn(mpsl_task(mpsl));
+    spawner.must_spawn(display_task(board.display));
 
-    run(sdc).await;
+    run(sdc, board.saadc).await;
 }
 
-pub async fn run<C>(controller: C)
+pub async fn run<C>(controller: C, saadc: embassy_nrf::peripherals::SAADC)
 where
     C: Controller,
 {
-    // Using a fixed "random" address can be useful for testing. In real scenarios, one would
-    // use e.g. the MAC 6 byte array as the address (how to get that varies by the platform).
-    let address = Address::random([0x42, 0x6A, 0xE3, 0x1E, 0x83, 0xE7]);
+    let address = Microbit::device_address();
     info!("Our address = {:?}", address);
 
+    let mut battery = BatteryMonitor::new(saadc);
+
     let mut resources = Resources::new(PacketQos::None);
-    let (stack, mut peripheral, _, runner) = trouble_host::new(controller, &mut resources)
+    let (stack, mut peripheral, central, runner) = trouble_host::new(controller, &mut resources)
         .set_random_address(address)
         .build();
+    let scanner = Scanner::new(central);
 
     info!("Starting advertising and GATT service");
     let server = Server::new_with_config(GapConfig::Peripheral(PeripheralConfig {
@@ -76,16 +94,25 @@ where
     }))
     .expect("Failed to create GATT server");
 
+    let dis = &server.device_information_service;
+    let _ = server.set(&dis.model_number, &"micro:bit v2".try_into().unwrap());
+    let _ = server.set(&dis.manufacturer_name, &"BBC".try_into().unwrap());
+    let _ = server.set(&dis.firmware_revision, &env!("CARGO_PKG_VERSION").try_into().unwrap());
+    let _ = server.set(&dis.serial_number, &dis::device_id());
+
     let app_task = async {
         loop {
+            CONN_STATE.signal(ConnectionState::Advertising);
             match advertise("Trouble Example", &mut peripheral).await {
                 Ok(conn) => {
+                    CONN_STATE.signal(ConnectionState::Connected);
                     // set up tasks when the connection is established to a central, so they don't run when no one is connected.
                     let a = gatt_events_task(&server, &conn);
-                    let b = custom_task(&server, &conn, stack);
+                    let b = custom_task(&server, &conn, stack, &mut battery);
                     // run until any task ends (usually because the connection has been closed),
                     // then return to advertising state.
                     select(a, b).await;
+                    CONN_STATE.signal(ConnectionState::Disconnected);
                 }
                 Err(e) => {
                     let e = defmt::Debug2Format(&e);
@@ -94,7 +121,64 @@ where
             }
         }
     };
-    select(ble_task(runner), app_task).await;
+    select3(ble_task(runner), app_task, central_task(scanner)).await;
+}
+
+/// How long `central_task` keeps an outbound connection open before
+/// disconnecting and resuming scanning.
+const CENTRAL_CONNECTION_HOLD: Duration = Duration::from_secs(10);
+
+/// Example task demonstrating the central/scanner role: periodically scan
+/// for nearby advertisers, connect to the first one found, and hold the
+/// link open instead of tearing it down immediately.
+///
+/// This runs the micro:bit as a collector/gateway alongside its peripheral
+/// role above; a real gateway would act on the connection instead of just
+/// logging it.
+async fn central_task<C: Controller>(mut scanner: Scanner<'_, C>) {
+    loop {
+        match scanner.scan(&[]).await {
+            Ok(report) => {
+                info!("[central] discovered advertiser {:?}", report.addr);
+                match scanner.connect(report.addr).await {
+                    Ok(conn) => {
+                        info!("[central] connected to {:?}", report.addr);
+                        hold_connection(&conn).await;
+                    }
+                    Err(e) => {
+                        let e = defmt::Debug2Format(&e);
+                        warn!("[central] connect error: {:?}", e);
+                    }
+                }
+            }
+            Err(e) => {
+                let e = defmt::Debug2Format(&e);
+                warn!("[central] scan error: {:?}", e);
+            }
+        }
+        Timer::after_secs(5).await;
+    }
+}
+
+/// Keep an outbound connection alive, logging GATT activity from the peer,
+/// until it disconnects or [`CENTRAL_CONNECTION_HOLD`] elapses.
+async fn hold_connection(conn: &Connection<'_>) {
+    let deadline = Instant::now() + CENTRAL_CONNECTION_HOLD;
+    loop {
+        match select(conn.next(), Timer::at(deadline)).await {
+            Either::First(ConnectionEvent::Disconnected { reason }) => {
+                info!("[central] peer disconnected: {:?}", reason);
+                return;
+            }
+            Either::First(ConnectionEvent::Gatt { .. }) => {
+                info!("[central] gatt event from peer");
+            }
+            Either::Second(()) => {
+                info!("[central] hold duration elapsed, disconnecting");
+                return;
+            }
+        }
+    }
 }
 
 /// This is a background task that is required to run forever alongside any other BLE tasks.
@@ -176,17 +260,24 @@ async fn advertise<'a, C: Controller>(
     Ok(conn)
 }
 
+/// How often `custom_task` polls the battery level and notifies the central.
+const BATTERY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
 /// Example task to use the BLE notifier interface.
-/// This task will notify the connected central of a counter value every 2 seconds.
-/// It will also read the RSSI value every 2 seconds.
-/// and will stop when the connection is closed by the central or an error occurs.
-async fn custom_task<C: Controller>(server: &Server<'_>, conn: &Connection<'_>, stack: Stack<'_, C>) {
-    let mut tick: u8 = 0;
+/// This task notifies the connected central of the battery level every
+/// [`BATTERY_POLL_INTERVAL`] and reads the RSSI value on the same cadence.
+/// It will stop when the connection is closed by the central or an error occurs.
+async fn custom_task<C: Controller>(
+    server: &Server<'_>,
+    conn: &Connection<'_>,
+    stack: Stack<'_, C>,
+    battery: &mut BatteryMonitor<'_>,
+) {
     let level = server.battery_service.level;
     loop {
-        tick = tick.wrapping_add(1);
-        info!("[custom_task] notifying connection of tick {}", tick);
-        if level.notify(server, conn, &tick).await.is_err() {
+        let percent = battery.poll(BATTERY_POLL_INTERVAL).await;
+        info!("[custom_task] notifying connection of battery level {}%", percent);
+        if level.notify(server, conn, &percent).await.is_err() {
             info!("[custom_task] error notifying connection");
             break;
         };
@@ -197,6 +288,5 @@ async fn custom_task<C: Controller>(server: &Server<'_>, conn: &Connection<'_>,
             info!("[custom_task] error getting RSSI");
             break;
         };
-        Timer::after_secs(2).await;
     }
 }
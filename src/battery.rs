@@ -0,0 +1,110 @@
+//! Battery level monitoring via the SAADC.
+//!
+//! Samples the supply voltage against the internal reference and maps it to
+//! a 0-100% battery level.
+
+use embassy_nrf::saadc::{ChannelConfig, Config, Gain, Reference, Resistor, Saadc, Time, VddInput};
+use embassy_nrf::{bind_interrupts, peripherals};
+use embassy_time::{Duration, Timer};
+
+bind_interrupts!(struct Irqs {
+    SAADC => embassy_nrf::saadc::InterruptHandler;
+});
+
+/// Supply voltage, in millivolts, considered an empty battery (e.g. 2x AAA).
+const BATTERY_MIN_MV: u16 = 2000;
+
+/// Supply voltage, in millivolts, considered a full battery (e.g. 2x AAA).
+const BATTERY_MAX_MV: u16 = 3000;
+
+/// Monitors the on-board battery rail through the SAADC's internal VDD input.
+pub struct BatteryMonitor<'d> {
+    saadc: Saadc<'d, 1>,
+}
+
+impl<'d> BatteryMonitor<'d> {
+    /// Configure the SAADC to sample VDD against the internal 0.6V
+    /// reference with a gain of 1/6 (full scale ~3.6V).
+    pub fn new(saadc: peripherals::SAADC) -> Self {
+        let channel_config = ChannelConfig {
+            gain: Gain::GAIN1_6,
+            reference: Reference::INTERNAL,
+            resistor: Resistor::BYPASS,
+            time: Time::_20US,
+            ..ChannelConfig::single_ended(&mut VddInput)
+        };
+        let config = Config::default();
+        let saadc = Saadc::new(saadc, Irqs, config, [channel_config]);
+        Self { saadc }
+    }
+
+    /// Sample the supply voltage once and return it in millivolts.
+    pub async fn read_mv(&mut self) -> u16 {
+        self.saadc.calibrate().await;
+        let mut buf = [0i16; 1];
+        self.saadc.sample(&mut buf).await;
+        let raw = buf[0].max(0) as u32;
+        (raw * 3600 / 4095) as u16
+    }
+
+    /// Sample the supply voltage and map it to a 0-100% battery level.
+    pub async fn read_percent(&mut self) -> u8 {
+        mv_to_percent(self.read_mv().await)
+    }
+
+    /// Wait for `interval`, then sample and return the battery percentage.
+    ///
+    /// Each call yields one value; calling it in a loop gives an update every
+    /// `interval`, e.g. from a task that calls `level.notify(...)` on each
+    /// value:
+    ///
+    /// ```ignore
+    /// loop {
+    ///     let percent = battery.poll(Duration::from_secs(60)).await;
+    ///     let _ = level.notify(server, conn, &percent).await;
+    /// }
+    /// ```
+    pub async fn poll(&mut self, interval: Duration) -> u8 {
+        Timer::after(interval).await;
+        self.read_percent().await
+    }
+}
+
+/// Map a supply voltage in millivolts to a 0-100% battery level, clamping to
+/// [`BATTERY_MIN_MV`]..=[`BATTERY_MAX_MV`]. Split out from
+/// [`BatteryMonitor::read_percent`] so the scaling math can be unit-tested
+/// without a SAADC.
+fn mv_to_percent(mv: u16) -> u8 {
+    let mv = mv.clamp(BATTERY_MIN_MV, BATTERY_MAX_MV);
+    let range = (BATTERY_MAX_MV - BATTERY_MIN_MV) as u32;
+    (((mv - BATTERY_MIN_MV) as u32 * 100) / range) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn below_min_clamps_to_zero() {
+        assert_eq!(mv_to_percent(0), 0);
+        assert_eq!(mv_to_percent(BATTERY_MIN_MV - 1), 0);
+    }
+
+    #[test]
+    fn above_max_clamps_to_full() {
+        assert_eq!(mv_to_percent(BATTERY_MAX_MV + 1), 100);
+        assert_eq!(mv_to_percent(u16::MAX), 100);
+    }
+
+    #[test]
+    fn exact_bounds_map_to_0_and_100() {
+        assert_eq!(mv_to_percent(BATTERY_MIN_MV), 0);
+        assert_eq!(mv_to_percent(BATTERY_MAX_MV), 100);
+    }
+
+    #[test]
+    fn midpoint_maps_to_50() {
+        let mid = BATTERY_MIN_MV + (BATTERY_MAX_MV - BATTERY_MIN_MV) / 2;
+        assert_eq!(mv_to_percent(mid), 50);
+    }
+}
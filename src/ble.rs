@@ -0,0 +1,258 @@
+//! BLE radio setup for the micro:bit.
+//!
+//! Wraps the `nrf-sdc` SoftDevice Controller and `nrf-mpsl` Multiprotocol
+//! Service Layer so an application only has to call [`Ble::init`] to get a
+//! [`Controller`](trouble_host::Controller) implementation, instead of
+//! configuring the SDC/MPSL resource pools and interrupt bindings by hand.
+
+use embassy_nrf::{pac, peripherals, rng};
+use nrf_sdc::{self as sdc, mpsl, SoftdeviceController};
+pub use mpsl::MultiprotocolServiceLayer;
+use static_cell::StaticCell;
+use trouble_host::prelude::*;
+
+/// Read the factory-programmed device address out of the FICR.
+///
+/// The nRF52833 has a unique address burned into `FICR.DEVICEADDR[0..2]` at
+/// manufacturing time, with `FICR.DEVICEADDRTYPE` recording whether it's a
+/// public or a random address. Use this as the default `set_random_address(...)`
+/// value instead of a hardcoded address so every board gets its own identity.
+pub fn device_address() -> Address {
+    let ficr = pac::FICR;
+    let low = ficr.deviceaddr(0).read();
+    let high = ficr.deviceaddr(1).read() as u16;
+    let is_random = ficr.deviceaddrtype().read().deviceaddrtype();
+
+    let bytes = [
+        (low & 0xff) as u8,
+        ((low >> 8) & 0xff) as u8,
+        ((low >> 16) & 0xff) as u8,
+        ((low >> 24) & 0xff) as u8,
+        (high & 0xff) as u8,
+        ((high >> 8) & 0xff) as u8,
+    ];
+
+    address_from_ficr_bytes(bytes, is_random)
+}
+
+/// Turn raw `FICR.DEVICEADDR` bytes into an [`Address`], setting the
+/// random-static address-type bits when `is_random` is set. Split out from
+/// [`device_address`] so the bit twiddling can be unit-tested without
+/// touching hardware.
+fn address_from_ficr_bytes(mut bytes: [u8; 6], is_random: bool) -> Address {
+    if is_random {
+        // A static random address must have its two most significant bits set.
+        bytes[5] |= 0xc0;
+        Address::random(bytes)
+    } else {
+        Address::new(AddrKind::PUBLIC, BdAddr::new(bytes))
+    }
+}
+
+/// Errors that can occur while bringing up the BLE radio.
+#[derive(Debug)]
+pub enum Error {
+    /// The SoftDevice Controller failed to initialize.
+    Sdc(sdc::Error),
+    /// The Multiprotocol Service Layer failed to initialize.
+    Mpsl(mpsl::Error),
+}
+
+impl From<sdc::Error> for Error {
+    fn from(e: sdc::Error) -> Self {
+        Self::Sdc(e)
+    }
+}
+
+impl From<mpsl::Error> for Error {
+    fn from(e: mpsl::Error) -> Self {
+        Self::Mpsl(e)
+    }
+}
+
+/// Peripherals reserved for the BLE radio, beyond the RNG.
+///
+/// The SoftDevice Controller and Multiprotocol Service Layer claim a fixed
+/// set of PPI channels plus RTC0 and TEMP for their own use; these aren't
+/// meaningful to an application so they're grouped here instead of on
+/// [`Microbit`](crate::Microbit) directly.
+pub struct BleResources {
+    pub rtc0: peripherals::RTC0,
+    pub temp: peripherals::TEMP,
+    pub timer0: peripherals::TIMER0,
+    pub ppi_ch17: peripherals::PPI_CH17,
+    pub ppi_ch18: peripherals::PPI_CH18,
+    pub ppi_ch19: peripherals::PPI_CH19,
+    pub ppi_ch20: peripherals::PPI_CH20,
+    pub ppi_ch21: peripherals::PPI_CH21,
+    pub ppi_ch22: peripherals::PPI_CH22,
+    pub ppi_ch23: peripherals::PPI_CH23,
+    pub ppi_ch24: peripherals::PPI_CH24,
+    pub ppi_ch25: peripherals::PPI_CH25,
+    pub ppi_ch26: peripherals::PPI_CH26,
+    pub ppi_ch30: peripherals::PPI_CH30,
+    pub ppi_ch31: peripherals::PPI_CH31,
+}
+
+/// Byte size of the SoftDevice Controller's shared memory pool, sized
+/// generously for one peripheral link and one central link. Shrink this
+/// once real buffer usage has been profiled on hardware.
+const SDC_MEMORY_SIZE: usize = 3312;
+
+/// Handle to the micro:bit's BLE radio.
+///
+/// Obtained as the `ble` field of [`Microbit`](crate::Microbit); call
+/// [`init`](Ble::init) once at startup to bring up the controller.
+pub struct Ble {
+    _private: (),
+}
+
+impl Ble {
+    pub(crate) fn new() -> Self {
+        Self { _private: () }
+    }
+
+    /// Bring up the SoftDevice Controller and Multiprotocol Service Layer.
+    ///
+    /// The controller is configured to support both the peripheral role
+    /// (advertise and accept connections) and the central role (scan and
+    /// initiate outbound connections), so it can be handed to
+    /// `trouble_host::new(...)` and used for either side of a link, or to
+    /// act as a collector/gateway that does both.
+    pub fn init(
+        &self,
+        resources: BleResources,
+        rng: peripherals::RNG,
+    ) -> Result<(SoftdeviceController<'static>, &'static MultiprotocolServiceLayer<'static>), Error> {
+        let mut rng = rng::Rng::new(rng, Irqs);
+
+        let mpsl_p = mpsl::Peripherals::new(
+            resources.rtc0,
+            resources.timer0,
+            resources.temp,
+            resources.ppi_ch19,
+            resources.ppi_ch30,
+            resources.ppi_ch31,
+        );
+        let lfclk_cfg = mpsl::raw::mpsl_clock_lfclk_cfg_t {
+            source: mpsl::raw::MPSL_CLOCK_LF_SRC_RC as u8,
+            rc_ctiv: mpsl::raw::MPSL_RECOMMENDED_RC_CTIV as u8,
+            rc_temp_ctiv: mpsl::raw::MPSL_RECOMMENDED_RC_TEMP_CTIV as u8,
+            accuracy_ppm: 500,
+            skip_wait_lfclk_started: false,
+        };
+        static MPSL: StaticCell<MultiprotocolServiceLayer> = StaticCell::new();
+        let mpsl = MPSL.init(MultiprotocolServiceLayer::new(mpsl_p, Irqs, lfclk_cfg)?);
+
+        let sdc_p = sdc::Peripherals::new(
+            resources.ppi_ch17,
+            resources.ppi_ch18,
+            resources.ppi_ch20,
+            resources.ppi_ch21,
+            resources.ppi_ch22,
+            resources.ppi_ch23,
+            resources.ppi_ch24,
+            resources.ppi_ch25,
+            resources.ppi_ch26,
+        );
+
+        static SDC_MEM: StaticCell<sdc::Mem<SDC_MEMORY_SIZE>> = StaticCell::new();
+        let sdc_mem = SDC_MEM.init(sdc::Mem::new());
+
+        let sdc = sdc::Builder::new()?
+            .support_adv()?
+            .support_peripheral()?
+            .support_scan()?
+            .support_central()?
+            .peripheral_count(1)?
+            .central_count(1)?
+            .build(sdc_p, &mut rng, mpsl, sdc_mem)?;
+
+        Ok((sdc, mpsl))
+    }
+}
+
+embassy_nrf::bind_interrupts!(struct Irqs {
+    RNG => rng::InterruptHandler<peripherals::RNG>;
+    EGU0_SWI0 => mpsl::LowPrioInterruptHandler;
+    CLOCK_POWER => mpsl::ClockInterruptHandler;
+    RADIO => mpsl::HighPrioInterruptHandler;
+    TIMER0 => mpsl::HighPrioInterruptHandler;
+    RTC0 => mpsl::HighPrioInterruptHandler;
+});
+
+/// A BLE central/scanner handle for the micro:bit.
+///
+/// This is the third element of the tuple returned by
+/// `trouble_host::new(...).build()`, wrapped with a couple of
+/// micro:bit-flavoured conveniences: scanning restricted to an address
+/// accept-list, and connecting directly off a discovered advertiser.
+pub struct Scanner<'d, C: Controller> {
+    central: Central<'d, C>,
+}
+
+impl<'d, C: Controller> Scanner<'d, C> {
+    /// Wrap a [`Central`] handle obtained from `trouble_host::new(...).build()`.
+    pub fn new(central: Central<'d, C>) -> Self {
+        Self { central }
+    }
+
+    /// Scan for advertisers.
+    ///
+    /// If `accept_list` is non-empty, only advertisers whose address
+    /// appears in it are reported; an empty list disables accept-list
+    /// filtering so any advertiser is reported.
+    pub async fn scan(&mut self, accept_list: &[Address]) -> Result<ScanReport, BleHostError<C::Error>> {
+        let config = if accept_list.is_empty() {
+            ScanConfig::default()
+        } else {
+            ScanConfig {
+                filter_accept_list: accept_list,
+                ..Default::default()
+            }
+        };
+        self.central.scan(&config).await
+    }
+
+    /// Initiate an outbound connection to `target`.
+    pub async fn connect(&mut self, target: Address) -> Result<Connection<'d>, BleHostError<C::Error>> {
+        let config = ConnectConfig {
+            connect_params: Default::default(),
+            scan_config: ScanConfig {
+                filter_accept_list: &[target],
+                ..Default::default()
+            },
+        };
+        self.central.connect(&config).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn public_address_bytes_are_unchanged() {
+        let bytes = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
+        let addr = address_from_ficr_bytes(bytes, false);
+        assert_eq!(addr.kind, AddrKind::PUBLIC);
+        assert_eq!(addr.addr.into_inner(), bytes);
+    }
+
+    #[test]
+    fn random_address_gets_static_bits_set() {
+        let bytes = [0x01, 0x02, 0x03, 0x04, 0x05, 0x3f];
+        let addr = address_from_ficr_bytes(bytes, true);
+        assert_eq!(addr.kind, AddrKind::RANDOM);
+        let raw = addr.addr.into_inner();
+        assert_eq!(raw[5] & 0xc0, 0xc0);
+        assert_eq!(raw[..5], bytes[..5]);
+    }
+
+    #[test]
+    fn random_address_bits_already_set_are_left_alone() {
+        let bytes = [0x01, 0x02, 0x03, 0x04, 0x05, 0xc0];
+        let addr = address_from_ficr_bytes(bytes, true);
+        assert_eq!(addr.addr.into_inner()[5], 0xc0);
+    }
+}
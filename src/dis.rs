@@ -0,0 +1,53 @@
+//! Device Information Service (0x180A).
+//!
+//! A ready-made `#[gatt_service]` definition for the standard characteristics
+//! most peripherals expose: model number, manufacturer, firmware/hardware
+//! revision, and serial number. Compose it into a `#[gatt_server]` struct
+//! alongside e.g. `BatteryService`:
+//!
+//! ```ignore
+//! #[gatt_server]
+//! struct Server {
+//!     battery_service: BatteryService,
+//!     device_information_service: DeviceInformationService,
+//! }
+//! ```
+
+use trouble_host::prelude::*;
+
+/// Device Information Service, see Bluetooth SIG service 0x180A.
+#[gatt_service(uuid = service::DEVICE_INFORMATION)]
+pub struct DeviceInformationService {
+    /// Model number, characteristic 0x2A24.
+    #[characteristic(uuid = characteristic::MODEL_NUMBER_STRING, read)]
+    pub model_number: heapless::String<32>,
+    /// Manufacturer name, characteristic 0x2A29.
+    #[characteristic(uuid = characteristic::MANUFACTURER_NAME_STRING, read)]
+    pub manufacturer_name: heapless::String<32>,
+    /// Firmware revision, characteristic 0x2A26.
+    #[characteristic(uuid = characteristic::FIRMWARE_REVISION_STRING, read)]
+    pub firmware_revision: heapless::String<16>,
+    /// Hardware revision, characteristic 0x2A27.
+    #[characteristic(uuid = characteristic::HARDWARE_REVISION_STRING, read)]
+    pub hardware_revision: heapless::String<16>,
+    /// Serial number, characteristic 0x2A25. Empty until set; pass
+    /// [`device_id`] to `server.set(...)` after constructing the `Server`
+    /// to populate it from the chip's FICR device ID.
+    #[characteristic(uuid = characteristic::SERIAL_NUMBER_STRING, read)]
+    pub serial_number: heapless::String<32>,
+}
+
+/// Format this chip's factory-programmed FICR device ID as a serial number.
+///
+/// `FICR.DEVICEID[0..2]` is a 64-bit value unique to each chip; formatting
+/// it as hex gives every board a distinct serial number for free.
+pub fn device_id() -> heapless::String<32> {
+    use core::fmt::Write;
+    let ficr = embassy_nrf::pac::FICR;
+    let low = ficr.deviceid(0).read();
+    let high = ficr.deviceid(1).read();
+
+    let mut id = heapless::String::new();
+    let _ = write!(id, "{high:08X}{low:08X}");
+    id
+}
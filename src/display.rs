@@ -0,0 +1,124 @@
+//! 5x5 LED matrix driver and status indicators.
+
+use embassy_futures::select::{select, Either};
+use embassy_nrf::gpio::{AnyPin, Level, Output, OutputDrive};
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::signal::Signal;
+use embassy_time::{Duration, Timer};
+
+/// A single frame for the 5x5 LED matrix: `true` means the LED is lit.
+pub type Frame = [[bool; 5]; 5];
+
+/// An all-dark frame.
+pub const BLANK: Frame = [[false; 5]; 5];
+
+/// Driver for the micro:bit's 5x5 LED matrix.
+///
+/// The matrix is multiplexed over 5 row and 5 column GPIOs; displaying a
+/// [`Frame`] means repeatedly scanning through the rows fast enough that
+/// persistence of vision makes it look like a static image.
+pub struct LedMatrix {
+    rows: [Output<'static>; 5],
+    cols: [Output<'static>; 5],
+}
+
+impl LedMatrix {
+    /// Create a new driver from the micro:bit's row/column GPIOs.
+    pub fn new(rows: [AnyPin; 5], cols: [AnyPin; 5]) -> Self {
+        Self {
+            rows: rows.map(|p| Output::new(p, Level::Low, OutputDrive::Standard)),
+            cols: cols.map(|p| Output::new(p, Level::High, OutputDrive::Standard)),
+        }
+    }
+
+    /// Scan the matrix once, holding each row for `row_duration`.
+    pub async fn render(&mut self, frame: &Frame, row_duration: Duration) {
+        for (r, row) in self.rows.iter_mut().enumerate() {
+            for (c, col) in self.cols.iter_mut().enumerate() {
+                if frame[r][c] {
+                    col.set_low();
+                } else {
+                    col.set_high();
+                }
+            }
+            row.set_high();
+            Timer::after(row_duration).await;
+            row.set_low();
+        }
+    }
+
+    /// Clear the display.
+    pub async fn clear(&mut self) {
+        self.render(&BLANK, Duration::from_micros(1)).await;
+    }
+}
+
+/// BLE connection lifecycle states, as tracked by the `trouble` example's
+/// advertise/accept loop.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, defmt::Format)]
+pub enum ConnectionState {
+    /// Advertising, waiting for a central to connect.
+    Advertising,
+    /// A central is connected.
+    Connected,
+    /// The connection was dropped; about to resume advertising.
+    Disconnected,
+}
+
+/// Shared signal used to report [`ConnectionState`] transitions to
+/// [`connection_indicator`].
+pub type ConnectionStateSignal = Signal<CriticalSectionRawMutex, ConnectionState>;
+
+const ADVERTISING_FRAME: Frame = {
+    let mut f = BLANK;
+    f[2][2] = true;
+    f
+};
+
+const CONNECTED_FRAME: Frame = [
+    [false, false, false, false, false],
+    [false, false, false, false, true],
+    [false, false, false, true, false],
+    [true, false, true, false, false],
+    [false, true, false, false, false],
+];
+
+/// Drive the LED matrix to reflect BLE connection state changes reported on
+/// `signal`: a slow pulse while advertising, a steady checkmark icon when
+/// connected, and blank on disconnect.
+///
+/// Run this as its own task alongside the BLE tasks; call
+/// `signal.signal(state)` from the advertise/accept loop on each
+/// transition.
+pub async fn connection_indicator(display: &mut LedMatrix, signal: &ConnectionStateSignal) -> ! {
+    let mut state = ConnectionState::Disconnected;
+    display.clear().await;
+    loop {
+        let animate = async {
+            loop {
+                match state {
+                    ConnectionState::Advertising => {
+                        display.render(&ADVERTISING_FRAME, Duration::from_millis(2)).await;
+                        display.clear().await;
+                        Timer::after(Duration::from_millis(400)).await;
+                    }
+                    ConnectionState::Connected => {
+                        display.render(&CONNECTED_FRAME, Duration::from_millis(2)).await;
+                    }
+                    ConnectionState::Disconnected => {
+                        // Nothing to animate while idle; wait for a transition.
+                        core::future::pending::<()>().await;
+                    }
+                }
+            }
+        };
+
+        match select(animate, signal.wait()).await {
+            Either::First(()) => unreachable!(),
+            Either::Second(next) => {
+                state = next;
+                display.clear().await;
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Board support package for the BBC micro:bit (nRF52833).
+//!
+//! [`Microbit::new`] takes care of `embassy_nrf::init` and hands back the
+//! on-board peripherals grouped by subsystem, so applications don't have to
+//! wire up pin assignments and interrupt bindings themselves.
+#![no_std]
+
+pub mod battery;
+pub mod ble;
+pub mod dis;
+pub mod display;
+
+use embassy_nrf::gpio::AnyPin;
+use embassy_nrf::peripherals;
+
+pub use ble::{Ble, BleResources};
+pub use display::LedMatrix;
+
+/// Board configuration, passed to [`Microbit::new`].
+#[derive(Default)]
+pub struct Config {
+    /// Low-level `embassy-nrf` peripheral configuration.
+    pub embassy_config: embassy_nrf::config::Config,
+}
+
+/// BBC micro:bit board resources.
+pub struct Microbit {
+    /// BLE radio (SoftDevice Controller + Multiprotocol Service Layer).
+    pub ble: Ble,
+    /// Peripherals reserved for [`Ble::init`], passed through unopened.
+    pub ble_resources: BleResources,
+    /// Hardware RNG, used to seed the BLE stack.
+    pub rng: peripherals::RNG,
+    /// ADC used by [`battery::BatteryMonitor`] to sample the supply voltage.
+    pub saadc: peripherals::SAADC,
+    /// The 5x5 LED matrix.
+    pub display: LedMatrix,
+}
+
+impl Microbit {
+    /// Initialize the board peripherals with the given configuration.
+    pub fn new(config: Config) -> Self {
+        let p = embassy_nrf::init(config.embassy_config);
+        let rows: [AnyPin; 5] = [
+            p.P0_21.into(),
+            p.P0_22.into(),
+            p.P0_15.into(),
+            p.P0_24.into(),
+            p.P0_19.into(),
+        ];
+        let cols: [AnyPin; 5] = [
+            p.P0_28.into(),
+            p.P0_11.into(),
+            p.P0_31.into(),
+            p.P1_05.into(),
+            p.P0_30.into(),
+        ];
+        Self {
+            ble: Ble::new(),
+            ble_resources: BleResources {
+                rtc0: p.RTC0,
+                temp: p.TEMP,
+                timer0: p.TIMER0,
+                ppi_ch17: p.PPI_CH17,
+                ppi_ch18: p.PPI_CH18,
+                ppi_ch19: p.PPI_CH19,
+                ppi_ch20: p.PPI_CH20,
+                ppi_ch21: p.PPI_CH21,
+                ppi_ch22: p.PPI_CH22,
+                ppi_ch23: p.PPI_CH23,
+                ppi_ch24: p.PPI_CH24,
+                ppi_ch25: p.PPI_CH25,
+                ppi_ch26: p.PPI_CH26,
+                ppi_ch30: p.PPI_CH30,
+                ppi_ch31: p.PPI_CH31,
+            },
+            rng: p.RNG,
+            saadc: p.SAADC,
+            display: LedMatrix::new(rows, cols),
+        }
+    }
+
+    /// See [`ble::device_address`].
+    pub fn device_address() -> trouble_host::prelude::Address {
+        ble::device_address()
+    }
+}